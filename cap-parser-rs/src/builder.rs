@@ -0,0 +1,142 @@
+//! A builder for constructing [`Alert`]s programmatically, so downstream
+//! tools can author alerts and feed them back through [`Alert::to_xml`]
+//! without hand-assembling XML.
+
+use crate::datetime::CapTimestamp;
+use crate::version::CapVersion;
+use crate::vocab::{MsgType, Scope, Status};
+use crate::{Alert, AlertInfo};
+use chrono::{DateTime, FixedOffset};
+use std::fmt;
+
+#[derive(Debug, Default)]
+pub struct AlertBuilder {
+    identifier: Option<String>,
+    sender: Option<String>,
+    sent: Option<DateTime<FixedOffset>>,
+    status: Option<Status>,
+    msg_type: Option<MsgType>,
+    scope: Option<Scope>,
+    source: Option<String>,
+    restriction: Option<String>,
+    addresses: Option<String>,
+    code: Vec<String>,
+    note: Option<String>,
+    references: Option<String>,
+    incidents: Option<String>,
+    info: Vec<AlertInfo>,
+}
+
+#[derive(Debug)]
+pub enum BuilderError {
+    MissingField(&'static str),
+}
+
+impl fmt::Display for BuilderError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            BuilderError::MissingField(field) => write!(f, "missing required field: {field}"),
+        }
+    }
+}
+
+impl std::error::Error for BuilderError {}
+
+impl Alert {
+    pub fn builder() -> AlertBuilder {
+        AlertBuilder::default()
+    }
+}
+
+impl AlertBuilder {
+    pub fn identifier(mut self, identifier: impl Into<String>) -> Self {
+        self.identifier = Some(identifier.into());
+        self
+    }
+
+    pub fn sender(mut self, sender: impl Into<String>) -> Self {
+        self.sender = Some(sender.into());
+        self
+    }
+
+    pub fn sent(mut self, sent: DateTime<FixedOffset>) -> Self {
+        self.sent = Some(sent);
+        self
+    }
+
+    pub fn status(mut self, status: Status) -> Self {
+        self.status = Some(status);
+        self
+    }
+
+    pub fn msg_type(mut self, msg_type: MsgType) -> Self {
+        self.msg_type = Some(msg_type);
+        self
+    }
+
+    pub fn scope(mut self, scope: Scope) -> Self {
+        self.scope = Some(scope);
+        self
+    }
+
+    pub fn source(mut self, source: impl Into<String>) -> Self {
+        self.source = Some(source.into());
+        self
+    }
+
+    pub fn restriction(mut self, restriction: impl Into<String>) -> Self {
+        self.restriction = Some(restriction.into());
+        self
+    }
+
+    pub fn addresses(mut self, addresses: impl Into<String>) -> Self {
+        self.addresses = Some(addresses.into());
+        self
+    }
+
+    pub fn code(mut self, code: impl Into<String>) -> Self {
+        self.code.push(code.into());
+        self
+    }
+
+    pub fn note(mut self, note: impl Into<String>) -> Self {
+        self.note = Some(note.into());
+        self
+    }
+
+    pub fn references(mut self, references: impl Into<String>) -> Self {
+        self.references = Some(references.into());
+        self
+    }
+
+    pub fn incidents(mut self, incidents: impl Into<String>) -> Self {
+        self.incidents = Some(incidents.into());
+        self
+    }
+
+    pub fn info(mut self, info: AlertInfo) -> Self {
+        self.info.push(info);
+        self
+    }
+
+    pub fn build(self) -> Result<Alert, BuilderError> {
+        Ok(Alert {
+            identifier: self.identifier.ok_or(BuilderError::MissingField("identifier"))?,
+            sender: self.sender.ok_or(BuilderError::MissingField("sender"))?,
+            sent: CapTimestamp::Parsed(self.sent.ok_or(BuilderError::MissingField("sent"))?),
+            status: self.status.ok_or(BuilderError::MissingField("status"))?,
+            msg_type: self.msg_type.ok_or(BuilderError::MissingField("msgType"))?,
+            source: self.source,
+            scope: self.scope.ok_or(BuilderError::MissingField("scope"))?,
+            restriction: self.restriction,
+            addresses: self.addresses,
+            code: (!self.code.is_empty()).then_some(self.code),
+            note: self.note,
+            references: self.references,
+            incidents: self.incidents,
+            info: (!self.info.is_empty()).then_some(self.info),
+            signature: None,
+            version: CapVersion::V1_2,
+        })
+    }
+}
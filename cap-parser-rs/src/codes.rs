@@ -0,0 +1,190 @@
+//! Decoding for the opaque codes CAP/IPAWS alerts carry alongside their
+//! free text: SAME event codes (`eventCode` `valueName="SAME"`), EAS
+//! originator codes (`parameter` `valueName="EAS-ORG"`), and SAME
+//! geographic codes (`geocode` `valueName="SAME"`).
+//!
+//! Lookup tables are built with `phf` so they cost nothing at runtime
+//! beyond the static data itself — no hashmap construction, no heap
+//! allocation for the tables.
+
+use phf::phf_map;
+
+/// The broad class of a SAME event code, used by EAS/weather-radio
+/// hardware to decide things like which codes trigger an alert tone.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum SameSeverityClass {
+    Warning,
+    Watch,
+    Emergency,
+    Statement,
+    Administrative,
+    Test,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct SameEventCode {
+    pub name: &'static str,
+    pub class: SameSeverityClass,
+}
+
+const fn event(name: &'static str, class: SameSeverityClass) -> SameEventCode {
+    SameEventCode { name, class }
+}
+
+/// SAME event codes as assigned by NWS/FCC (47 CFR Part 11). Not every
+/// code ever issued is present; codes seen in the wild but missing here
+/// should be added rather than worked around downstream.
+pub static SAME_EVENT_CODES: phf::Map<&'static str, SameEventCode> = phf_map! {
+    "EAN" => event("Emergency Action Notification", SameSeverityClass::Emergency),
+    "EAT" => event("Emergency Action Termination", SameSeverityClass::Administrative),
+    "NIC" => event("National Information Center", SameSeverityClass::Administrative),
+    "RMT" => event("Required Monthly Test", SameSeverityClass::Test),
+    "RWT" => event("Required Weekly Test", SameSeverityClass::Test),
+    "ADR" => event("Administrative Message", SameSeverityClass::Administrative),
+    "AVW" => event("Avalanche Warning", SameSeverityClass::Warning),
+    "AVA" => event("Avalanche Watch", SameSeverityClass::Watch),
+    "BZW" => event("Blizzard Warning", SameSeverityClass::Warning),
+    "CAE" => event("Child Abduction Emergency", SameSeverityClass::Emergency),
+    "CDW" => event("Civil Danger Warning", SameSeverityClass::Warning),
+    "CEM" => event("Civil Emergency Message", SameSeverityClass::Emergency),
+    "CFW" => event("Coastal Flood Warning", SameSeverityClass::Warning),
+    "CFA" => event("Coastal Flood Watch", SameSeverityClass::Watch),
+    "DSW" => event("Dust Storm Warning", SameSeverityClass::Warning),
+    "EQW" => event("Earthquake Warning", SameSeverityClass::Warning),
+    "EVI" => event("Evacuation Immediate", SameSeverityClass::Emergency),
+    "FRW" => event("Fire Warning", SameSeverityClass::Warning),
+    "FFW" => event("Flash Flood Warning", SameSeverityClass::Warning),
+    "FFA" => event("Flash Flood Watch", SameSeverityClass::Watch),
+    "FFS" => event("Flash Flood Statement", SameSeverityClass::Statement),
+    "FLW" => event("Flood Warning", SameSeverityClass::Warning),
+    "FLA" => event("Flood Watch", SameSeverityClass::Watch),
+    "FLS" => event("Flood Statement", SameSeverityClass::Statement),
+    "HMW" => event("Hazardous Materials Warning", SameSeverityClass::Warning),
+    "HWW" => event("High Wind Warning", SameSeverityClass::Warning),
+    "HWA" => event("High Wind Watch", SameSeverityClass::Watch),
+    "HUW" => event("Hurricane Warning", SameSeverityClass::Warning),
+    "HUA" => event("Hurricane Watch", SameSeverityClass::Watch),
+    "HLS" => event("Hurricane Statement", SameSeverityClass::Statement),
+    "LEW" => event("Law Enforcement Warning", SameSeverityClass::Warning),
+    "LAE" => event("Local Area Emergency", SameSeverityClass::Emergency),
+    "NMN" => event("Network Message Notification", SameSeverityClass::Administrative),
+    "NUW" => event("Nuclear Power Plant Warning", SameSeverityClass::Warning),
+    "DMO" => event("Practice/Demo Warning", SameSeverityClass::Test),
+    "RHW" => event("Radiological Hazard Warning", SameSeverityClass::Warning),
+    "SVR" => event("Severe Thunderstorm Warning", SameSeverityClass::Warning),
+    "SVA" => event("Severe Thunderstorm Watch", SameSeverityClass::Watch),
+    "SVS" => event("Severe Weather Statement", SameSeverityClass::Statement),
+    "SPW" => event("Shelter In Place Warning", SameSeverityClass::Warning),
+    "SMW" => event("Special Marine Warning", SameSeverityClass::Warning),
+    "SPS" => event("Special Weather Statement", SameSeverityClass::Statement),
+    "TOR" => event("Tornado Warning", SameSeverityClass::Warning),
+    "TOA" => event("Tornado Watch", SameSeverityClass::Watch),
+    "TRW" => event("Tropical Storm Warning", SameSeverityClass::Warning),
+    "TRA" => event("Tropical Storm Watch", SameSeverityClass::Watch),
+    "TSW" => event("Tsunami Warning", SameSeverityClass::Warning),
+    "TSA" => event("Tsunami Watch", SameSeverityClass::Watch),
+    "VOW" => event("Volcano Warning", SameSeverityClass::Warning),
+    "WSW" => event("Winter Storm Warning", SameSeverityClass::Warning),
+    "WSA" => event("Winter Storm Watch", SameSeverityClass::Watch),
+};
+
+/// EAS originator codes (`parameter` `valueName="EAS-ORG"`).
+pub static EAS_ORIGINATOR_CODES: phf::Map<&'static str, &'static str> = phf_map! {
+    "CIV" => "Civil Authorities",
+    "WXR" => "National Weather Service",
+    "EAS" => "Broadcast Station or Cable System",
+    "PEP" => "Primary Entry Point Station",
+};
+
+/// A decoded SAME geographic code: a subdivision digit (0 = entire
+/// county), a 2-digit state FIPS code, and a 3-digit county FIPS code.
+/// County-level names require a full FIPS gazetteer this crate doesn't
+/// ship; only the state name is resolved here.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct FipsGeocode {
+    pub subdivision: u8,
+    pub state_fips: String,
+    pub county_fips: String,
+    pub state_name: Option<&'static str>,
+}
+
+/// 2-digit state/territory FIPS codes (INCITS 38 / ANSI).
+pub static STATE_FIPS: phf::Map<&'static str, &'static str> = phf_map! {
+    "01" => "Alabama", "02" => "Alaska", "04" => "Arizona", "05" => "Arkansas",
+    "06" => "California", "08" => "Colorado", "09" => "Connecticut", "10" => "Delaware",
+    "11" => "District of Columbia", "12" => "Florida", "13" => "Georgia", "15" => "Hawaii",
+    "16" => "Idaho", "17" => "Illinois", "18" => "Indiana", "19" => "Iowa",
+    "20" => "Kansas", "21" => "Kentucky", "22" => "Louisiana", "23" => "Maine",
+    "24" => "Maryland", "25" => "Massachusetts", "26" => "Michigan", "27" => "Minnesota",
+    "28" => "Mississippi", "29" => "Missouri", "30" => "Montana", "31" => "Nebraska",
+    "32" => "Nevada", "33" => "New Hampshire", "34" => "New Jersey", "35" => "New Mexico",
+    "36" => "New York", "37" => "North Carolina", "38" => "North Dakota", "39" => "Ohio",
+    "40" => "Oklahoma", "41" => "Oregon", "42" => "Pennsylvania", "44" => "Rhode Island",
+    "45" => "South Carolina", "46" => "South Dakota", "47" => "Tennessee", "48" => "Texas",
+    "49" => "Utah", "50" => "Vermont", "51" => "Virginia", "53" => "Washington",
+    "54" => "West Virginia", "55" => "Wisconsin", "56" => "Wyoming",
+    "60" => "American Samoa", "66" => "Guam", "69" => "Northern Mariana Islands",
+    "72" => "Puerto Rico", "78" => "U.S. Virgin Islands",
+};
+
+/// Looks up a SAME event code (e.g. `"LAE"`) as carried in `eventCode`.
+pub fn same_event(code: &str) -> Option<&'static SameEventCode> {
+    SAME_EVENT_CODES.get(code)
+}
+
+/// Looks up an EAS originator code (e.g. `"CIV"`) as carried in the
+/// `EAS-ORG` parameter.
+pub fn eas_originator(code: &str) -> Option<&'static str> {
+    EAS_ORIGINATOR_CODES.get(code).copied()
+}
+
+/// Decodes a 6-digit SAME geographic code (e.g. `"013189"`) into its
+/// subdivision digit, state FIPS, and county FIPS components.
+pub fn decode_same_geocode(code: &str) -> Option<FipsGeocode> {
+    let code = code.trim();
+    if code.len() != 6 || !code.bytes().all(|b| b.is_ascii_digit()) {
+        return None;
+    }
+    let subdivision = code[0..1].parse().ok()?;
+    let state_fips = code[1..3].to_string();
+    let county_fips = code[3..6].to_string();
+    let state_name = STATE_FIPS.get(state_fips.as_str()).copied();
+    Some(FipsGeocode { subdivision, state_fips, county_fips, state_name })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn same_event_decodes_a_known_code() {
+        let event = same_event("TOR").expect("TOR is a known SAME code");
+        assert_eq!(event.name, "Tornado Warning");
+        assert_eq!(event.class, SameSeverityClass::Warning);
+    }
+
+    #[test]
+    fn same_event_returns_none_for_an_unknown_code() {
+        assert!(same_event("ZZZ").is_none());
+    }
+
+    #[test]
+    fn eas_originator_decodes_a_known_code() {
+        assert_eq!(eas_originator("WXR"), Some("National Weather Service"));
+    }
+
+    #[test]
+    fn decode_same_geocode_splits_subdivision_state_and_county() {
+        let geocode = decode_same_geocode("013189").expect("well-formed 6-digit code");
+        assert_eq!(geocode.subdivision, 0);
+        assert_eq!(geocode.state_fips, "13");
+        assert_eq!(geocode.county_fips, "189");
+        assert_eq!(geocode.state_name, Some("Georgia"));
+    }
+
+    #[test]
+    fn decode_same_geocode_rejects_the_wrong_length_or_non_digits() {
+        assert!(decode_same_geocode("1234").is_none());
+        assert!(decode_same_geocode("01318x").is_none());
+    }
+}
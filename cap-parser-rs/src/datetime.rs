@@ -0,0 +1,107 @@
+//! Typed, timezone-aware CAP timestamps (`sent`, `effective`, `onset`,
+//! `expires`).
+//!
+//! CAP mandates RFC-3339-with-offset timestamps (e.g.
+//! `2025-01-30T14:58:26-05:00`) and forbids bare, offset-less local times.
+//! We preserve the original offset rather than normalizing to UTC, since
+//! the offset is often the only record of the alert's local timezone.
+//!
+//! A malformed timestamp on one `info` block shouldn't discard the whole
+//! alert, so deserialization never fails: an unparsable value is kept as
+//! [`CapTimestamp::Raw`] instead.
+
+use chrono::{DateTime, FixedOffset};
+use serde::{Deserialize, Deserializer, Serialize, Serializer};
+
+/// A CAP timestamp field, parsed when possible.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum CapTimestamp {
+    Parsed(DateTime<FixedOffset>),
+    /// The raw text, retained because it didn't parse as RFC 3339.
+    Raw(String),
+}
+
+impl CapTimestamp {
+    pub fn as_datetime(&self) -> Option<&DateTime<FixedOffset>> {
+        match self {
+            CapTimestamp::Parsed(dt) => Some(dt),
+            CapTimestamp::Raw(_) => None,
+        }
+    }
+
+    pub fn as_raw(&self) -> Option<&str> {
+        match self {
+            CapTimestamp::Raw(raw) => Some(raw.as_str()),
+            CapTimestamp::Parsed(_) => None,
+        }
+    }
+
+    /// The CAP wire text for this timestamp: RFC 3339 (preserving the
+    /// original offset) for `Parsed`, or the original raw text for `Raw`.
+    pub fn as_text(&self) -> String {
+        match self {
+            CapTimestamp::Parsed(dt) => dt.to_rfc3339(),
+            CapTimestamp::Raw(raw) => raw.clone(),
+        }
+    }
+}
+
+impl Serialize for CapTimestamp {
+    fn serialize<S>(&self, serializer: S) -> Result<S::Ok, S::Error>
+    where
+        S: Serializer,
+    {
+        match self {
+            // `to_rfc3339` preserves the `DateTime`'s original offset
+            // rather than normalizing to UTC.
+            CapTimestamp::Parsed(dt) => serializer.serialize_str(&dt.to_rfc3339()),
+            CapTimestamp::Raw(raw) => serializer.serialize_str(raw),
+        }
+    }
+}
+
+impl<'de> Deserialize<'de> for CapTimestamp {
+    fn deserialize<D>(deserializer: D) -> Result<Self, D::Error>
+    where
+        D: Deserializer<'de>,
+    {
+        let raw = String::deserialize(deserializer)?;
+        Ok(match DateTime::parse_from_rfc3339(raw.trim()) {
+            Ok(dt) => CapTimestamp::Parsed(dt),
+            Err(_) => CapTimestamp::Raw(raw),
+        })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use quick_xml::de::from_str;
+
+    #[derive(Deserialize)]
+    struct Wrapper {
+        sent: CapTimestamp,
+    }
+
+    #[test]
+    fn deserialize_parses_a_well_formed_rfc3339_timestamp() {
+        let wrapper: Wrapper =
+            from_str("<wrapper><sent>2025-01-30T14:58:26-05:00</sent></wrapper>").unwrap();
+        assert_eq!(wrapper.sent.as_raw(), None);
+        assert_eq!(wrapper.sent.as_datetime().unwrap().to_rfc3339(), "2025-01-30T14:58:26-05:00");
+    }
+
+    #[test]
+    fn deserialize_falls_back_to_raw_for_an_offsetless_local_time() {
+        let wrapper: Wrapper = from_str("<wrapper><sent>2025-01-30T14:58:26</sent></wrapper>").unwrap();
+        assert_eq!(wrapper.sent.as_datetime(), None);
+        assert_eq!(wrapper.sent.as_raw(), Some("2025-01-30T14:58:26"));
+    }
+
+    #[test]
+    fn as_text_preserves_the_original_offset() {
+        let wrapper: Wrapper =
+            from_str("<wrapper><sent>2025-01-30T14:58:26-05:00</sent></wrapper>").unwrap();
+        assert_eq!(wrapper.sent.as_text(), "2025-01-30T14:58:26-05:00");
+    }
+}
@@ -1,167 +1,354 @@
+mod builder;
+mod codes;
+mod datetime;
+mod signature;
+mod spatial;
+mod version;
+mod vocab;
+mod xml_writer;
+
+use chrono::Duration;
+use codes::{FipsGeocode, SameEventCode};
+use datetime::CapTimestamp;
+use spatial::{BoundingBox, Circle, Polygon};
 use quick_xml::de::from_str;
-use serde::Deserialize;
-use std::{error::Error, fs};
-
-#[derive(Debug, Deserialize)]
-enum Status {
-    Actual,
-    Excercise,
-    System,
-    Test,
-    Draft,
+use serde::{Deserialize, Serialize};
+use signature::{Signature, SignatureError, SignatureVerification};
+use std::error::Error;
+use version::CapVersion;
+use vocab::{Category, Certainty, MsgType, ResponseType, Scope, Severity, Status, Urgency};
+
+#[derive(Debug, Clone, Deserialize, Serialize)]
+pub struct Value {
+    #[serde(rename = "valueName")]
+    pub value_name: String,
+    pub value: String,
 }
 
-#[derive(Debug, Deserialize)]
-enum MsgType {
-    Alert,
-    Update,
-    Cancel,
-    Ack,
-    Error,
+#[derive(Debug, Clone, Deserialize, Serialize)]
+pub struct Resource {
+    #[serde(rename = "resourceDesc")]
+    pub resource_desc: String,
+    #[serde(rename = "mimeType")]
+    pub mime_type: String,
+    pub size: Option<String>,
+    pub uri: Option<String>,
+    #[serde(rename = "derefUri")]
+    pub deref_uri: Option<String>,
+    pub digest: Option<String>,
 }
 
-#[derive(Debug, Deserialize)]
-enum Scope {
-    Public,
-    Restricted,
-    Private,
+#[derive(Debug, Clone, Deserialize, Serialize)]
+pub struct Area {
+    #[serde(rename = "areaDesc")]
+    pub area_desc: String,
+    pub polygon: Option<Vec<String>>,
+    pub circle: Option<Vec<String>>,
+    pub geocode: Option<Vec<Value>>,
+    pub altitude: Option<String>,
+    pub ceiling: Option<String>,
 }
 
-#[derive(Debug, Deserialize)]
-enum Category {
-    Geo,
-    Met,
-    Safety,
-    Security,
-    Rescue,
-    Fire,
-    Health,
-    Env,
-    Transport,
-    Infra,
-    CBRNE,
-    Other,
+impl Area {
+    /// Decodes every `geocode` entry with `valueName="SAME"` into its FIPS
+    /// components. Geocodes using a different scheme (e.g. `UGC`) are
+    /// skipped since they're not SAME codes.
+    pub fn fips_counties(&self) -> Vec<FipsGeocode> {
+        self.geocode
+            .iter()
+            .flatten()
+            .filter(|value| value.value_name == "SAME")
+            .filter_map(|value| codes::decode_same_geocode(&value.value))
+            .collect()
+    }
+
+    /// Parses every `polygon` entry, silently skipping any that fail to
+    /// parse (malformed rings shouldn't stop the rest of the area from
+    /// being usable).
+    pub fn polygons(&self) -> Vec<Polygon> {
+        self.polygon
+            .iter()
+            .flatten()
+            .filter_map(|text| Polygon::parse(text).ok())
+            .collect()
+    }
+
+    /// Parses every `circle` entry, silently skipping any that fail to
+    /// parse.
+    pub fn circles(&self) -> Vec<Circle> {
+        self.circle
+            .iter()
+            .flatten()
+            .filter_map(|text| Circle::parse(text).ok())
+            .collect()
+    }
+
+    /// Whether `(lat, lon)` falls inside any of this area's polygons or
+    /// circles.
+    pub fn contains(&self, lat: f64, lon: f64) -> bool {
+        self.polygons().iter().any(|p| p.contains(lat, lon))
+            || self.circles().iter().any(|c| c.contains(lat, lon))
+    }
+
+    /// The bounding box enclosing every polygon and circle in this area,
+    /// or `None` if it has neither.
+    pub fn bounding_box(&self) -> Option<BoundingBox> {
+        let boxes = self
+            .polygons()
+            .iter()
+            .map(Polygon::bounding_box)
+            .chain(self.circles().iter().map(Circle::bounding_box))
+            .collect::<Vec<_>>();
+
+        boxes.into_iter().reduce(|a, b| BoundingBox {
+            min_lat: a.min_lat.min(b.min_lat),
+            max_lat: a.max_lat.max(b.max_lat),
+            min_lon: a.min_lon.min(b.min_lon),
+            max_lon: a.max_lon.max(b.max_lon),
+        })
+    }
 }
 
-#[derive(Debug, Deserialize)]
-enum ResponseType {
-    Shelter,
-    Evacuate,
-    Prepare,
-    Execute,
-    Avoid,
-    Monitor,
-    Assess,
-    AllClear,
-    None,
+#[derive(Debug, Clone, Deserialize, Serialize)]
+pub struct AlertInfo {
+    pub language: String,
+    pub category: Vec<Category>,
+    pub event: String,
+    #[serde(rename = "responseType")]
+    pub response_type: Option<ResponseType>,
+    pub urgency: Urgency,
+    pub severity: Severity,
+    pub certainty: Certainty,
+    pub audience: Option<String>,
+    #[serde(rename = "eventCode")]
+    pub event_codes: Option<Vec<Value>>,
+    pub effective: Option<CapTimestamp>,
+    pub onset: Option<CapTimestamp>,
+    pub expires: Option<CapTimestamp>,
+    #[serde(rename = "senderName")]
+    pub sender_name: Option<String>,
+    pub headline: Option<String>,
+    pub description: Option<String>,
+    pub instruction: Option<String>,
+    pub web: Option<String>,
+    pub contact: Option<String>,
+    pub parameter: Option<Vec<Value>>,
+    pub resource: Option<Vec<Resource>>,
+    pub area: Option<Vec<Area>>,
 }
 
-#[derive(Debug, Deserialize)]
-enum Urgency {
-    Immediate,
-    Expected,
-    Future,
-    Past,
-    Unknown,
+impl AlertInfo {
+    /// Looks up the decoded SAME event name and severity class for this
+    /// info block's `eventCode` entry with `valueName="SAME"`, if present.
+    pub fn same_event_name(&self) -> Option<&'static SameEventCode> {
+        self.event_codes
+            .iter()
+            .flatten()
+            .find(|value| value.value_name == "SAME")
+            .and_then(|value| codes::same_event(&value.value))
+    }
+
+    /// Looks up the decoded EAS originator name for this info block's
+    /// `EAS-ORG` parameter, if present.
+    pub fn eas_originator(&self) -> Option<&'static str> {
+        self.parameter
+            .iter()
+            .flatten()
+            .find(|value| value.value_name == "EAS-ORG")
+            .and_then(|value| codes::eas_originator(&value.value))
+    }
+
+    /// Whether this info block's `expires` time has passed `now`. Returns
+    /// `false` when `expires` is absent or failed to parse, since CAP
+    /// treats a missing expiry as "does not expire."
+    pub fn is_expired(&self, now: chrono::DateTime<chrono::FixedOffset>) -> bool {
+        self.expires
+            .as_ref()
+            .and_then(CapTimestamp::as_datetime)
+            .is_some_and(|expires| *expires < now)
+    }
+
+    /// The `(effective, expires)` window, or `None` when the start time is
+    /// absent or failed to parse. The end is `None` when `expires` is absent
+    /// or failed to parse. `effective` falls back to `onset` if `effective` is absent, matching
+    /// the CAP spec's guidance that `onset` is the intended effective time
+    /// when `effective` is not given.
+    pub fn effective_range(
+        &self,
+    ) -> Option<(chrono::DateTime<chrono::FixedOffset>, Option<chrono::DateTime<chrono::FixedOffset>>)> {
+        let start = self
+            .effective
+            .as_ref()
+            .or(self.onset.as_ref())
+            .and_then(CapTimestamp::as_datetime)?;
+        let end = self.expires.as_ref().and_then(CapTimestamp::as_datetime);
+        Some((*start, end.copied()))
+    }
+
+    /// Time remaining until `onset`, relative to `now`. `None` if `onset`
+    /// is absent, unparsable, or already in the past.
+    pub fn duration_until_onset(&self, now: chrono::DateTime<chrono::FixedOffset>) -> Option<Duration> {
+        let onset = self.onset.as_ref().and_then(CapTimestamp::as_datetime)?;
+        let remaining = *onset - now;
+        (remaining > Duration::zero()).then_some(remaining)
+    }
 }
 
-#[derive(Debug, Deserialize)]
-enum Severity {
-    Extreme,
-    Severe,
-    Moderate,
-    Minor,
-    Unknown,
+#[derive(Debug, Clone, Deserialize, Serialize)]
+pub struct Alert {
+    pub identifier: String,
+    pub sender: String,
+    pub sent: CapTimestamp,
+    pub status: Status,
+    #[serde(rename = "msgType")]
+    pub msg_type: MsgType,
+    pub source: Option<String>,
+    pub scope: Scope,
+    pub restriction: Option<String>,
+    pub addresses: Option<String>,
+    pub code: Option<Vec<String>>,
+    pub note: Option<String>,
+    pub references: Option<String>,
+    pub incidents: Option<String>,
+    pub info: Option<Vec<AlertInfo>>,
+    #[serde(rename = "Signature")]
+    pub signature: Option<Signature>,
+    /// The CAP revision this alert was parsed from, detected from the root
+    /// element's `xmlns` by [`version::detect`] rather than deserialized as
+    /// a regular field. See [`version`] for which fields are only
+    /// meaningful under some revisions.
+    #[serde(skip)]
+    pub version: CapVersion,
 }
 
-#[derive(Debug, Deserialize)]
-enum Certainty {
-    Observed,
-    Likely,
-    Possible,
-    Unlikely,
-    Unknown,
+impl Alert {
+    /// Verifies the enveloped XML-DSig `<Signature>` carried alongside this
+    /// alert, if any. `original_document` must be the exact CAP XML text
+    /// this `Alert` was deserialized from, since the digest covers the
+    /// document minus the `<Signature>` element itself.
+    ///
+    /// Returns `Ok(None)` when the alert carries no signature at all.
+    /// `SignatureVerification::UntrustedCertificate` is never produced
+    /// here; chaining the leaf certificate to the IdenTrust/IPAWS CA is the
+    /// caller's responsibility.
+    pub fn verify_signature(
+        &self,
+        original_document: &str,
+    ) -> Result<Option<SignatureVerification>, SignatureError> {
+        match &self.signature {
+            Some(sig) => sig.verify(original_document).map(Some),
+            None => Ok(None),
+        }
+    }
 }
-#[derive(Debug, Deserialize)]
-struct Value {
-    #[serde(rename = "valueName")]
-    value_name: String,
-    value: String,
+
+#[derive(Debug, Clone, Deserialize, Serialize)]
+pub struct AlertList {
+    #[serde(rename = "alert")]
+    pub alerts: Vec<Alert>,
 }
 
-#[derive(Debug, Deserialize)]
-struct Resource {
-    #[serde(rename = "resourceDesc")]
-    resource_desc: String,
-    #[serde(rename = "mimeType")]
-    mime_type: String,
-    size: Option<String>,
-    uri: Option<String>,
-    #[serde(rename = "derefUri")]
-    deref_uri: Option<String>,
-    digest: Option<String>,
+/// How [`parse`] should treat controlled-vocabulary tokens it doesn't
+/// recognize (see [`vocab`] for the affected enums).
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum ParseMode {
+    /// Reject documents containing unrecognized vocabulary. Use this for
+    /// CAP conformance testing.
+    Strict,
+    /// Accept unrecognized vocabulary, surfacing each occurrence as a
+    /// [`Warning`] so downstream code can still read the headline, area,
+    /// and parameters of a non-conformant alert.
+    Lenient,
 }
 
-#[derive(Debug, Deserialize)]
-struct Area {
-    #[serde(rename = "areaDesc")]
-    area_desc: String,
-    polygon: Option<Vec<String>>,
-    circle: Option<Vec<String>>,
-    geocode: Option<Vec<Value>>,
-    altitude: Option<String>,
-    ceiling: Option<String>,
+/// One controlled-vocabulary token that fell outside the CAP spec.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Warning {
+    /// Dotted path to the offending field, e.g. `"info[0].category[1]"`.
+    pub path: String,
+    /// The raw, unrecognized token.
+    pub raw_value: String,
 }
 
-#[derive(Debug, Deserialize)]
-struct AlertInfo {
-    language: String,
-    category: Vec<Category>,
-    event: String,
-    #[serde(rename = "responseType")]
-    response_type: Option<ResponseType>,
-    urgency: Urgency,
-    severity: Severity,
-    certainty: Certainty,
-    audiance: Option<String>,
-    event_codes: Option<Vec<Value>>,
-    effective: Option<String>,
-    onset: Option<String>,
-    expires: Option<String>,
-    sender_name: Option<String>,
-    headline: Option<String>,
-    description: Option<String>,
-    instruction: Option<String>,
-    web: Option<String>,
-    contact: Option<String>,
-    parameter: Option<Vec<Value>>,
-    resource: Option<Vec<Resource>>,
-    area: Option<Vec<Area>>,
+#[derive(Debug)]
+pub enum ParseError {
+    Xml(quick_xml::DeError),
+    UnknownVocabulary(Vec<Warning>),
 }
 
-#[derive(Debug, Deserialize)]
-struct Alert {
-    identifier: String,
-    sender: String,
-    sent: String,
-    status: Status,
-    #[serde(rename = "msgType")]
-    msg_type: MsgType,
-    source: Option<String>,
-    scope: Scope,
-    restriction: Option<String>,
-    addresses: Option<String>,
-    code: Option<Vec<String>>,
-    note: Option<String>,
-    references: Option<String>,
-    incidents: Option<String>,
-    info: Option<Vec<AlertInfo>>,
+impl std::fmt::Display for ParseError {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            ParseError::Xml(e) => write!(f, "{e}"),
+            ParseError::UnknownVocabulary(warnings) => {
+                write!(f, "{} unrecognized controlled-vocabulary token(s)", warnings.len())
+            }
+        }
+    }
 }
 
-#[derive(Debug, Deserialize)]
-struct AlertList {
-    #[serde(rename = "alert")]
-    alerts: Vec<Alert>,
+impl std::error::Error for ParseError {}
+
+/// Top-level entry point: deserializes a single `<alert>` document and, in
+/// [`ParseMode::Strict`], rejects it if any controlled-vocabulary field
+/// (`category`, `responseType`, `urgency`, `severity`, `certainty`,
+/// `msgType`, `status`, `scope`) held a token outside the CAP spec, or if a
+/// field is populated that the alert's own detected [`version::CapVersion`]
+/// doesn't support (see [`version::version_warnings`]).
+///
+/// In [`ParseMode::Lenient`] such tokens are retained (see [`vocab`]) and
+/// returned as [`Warning`]s alongside the parsed `Alert` rather than
+/// failing the parse.
+pub fn parse(xml: &str, mode: ParseMode) -> Result<(Alert, Vec<Warning>), ParseError> {
+    let mut alert: Alert = from_str(xml).map_err(ParseError::Xml)?;
+    alert.version = version::detect(xml);
+    let mut warnings = collect_vocabulary_warnings(&alert);
+    warnings.extend(version::version_warnings(&alert));
+
+    match mode {
+        ParseMode::Strict if !warnings.is_empty() => Err(ParseError::UnknownVocabulary(warnings)),
+        _ => Ok((alert, warnings)),
+    }
+}
+
+fn collect_vocabulary_warnings(alert: &Alert) -> Vec<Warning> {
+    let mut warnings = Vec::new();
+
+    push_warning(&mut warnings, "status", alert.status.unknown_value());
+    push_warning(&mut warnings, "msgType", alert.msg_type.unknown_value());
+    push_warning(&mut warnings, "scope", alert.scope.unknown_value());
+
+    for (i, info) in alert.info.iter().flatten().enumerate() {
+        for (j, category) in info.category.iter().enumerate() {
+            push_warning(
+                &mut warnings,
+                &format!("info[{i}].category[{j}]"),
+                category.unknown_value(),
+            );
+        }
+        if let Some(response_type) = &info.response_type {
+            push_warning(
+                &mut warnings,
+                &format!("info[{i}].responseType"),
+                response_type.unknown_value(),
+            );
+        }
+        push_warning(&mut warnings, &format!("info[{i}].urgency"), info.urgency.unknown_value());
+        push_warning(&mut warnings, &format!("info[{i}].severity"), info.severity.unknown_value());
+        push_warning(
+            &mut warnings,
+            &format!("info[{i}].certainty"),
+            info.certainty.unknown_value(),
+        );
+    }
+
+    warnings
+}
+
+fn push_warning(warnings: &mut Vec<Warning>, path: &str, unknown_value: Option<&str>) {
+    if let Some(raw_value) = unknown_value {
+        warnings.push(Warning { path: path.to_string(), raw_value: raw_value.to_string() });
+    }
 }
 
 fn main() -> Result<(), Box<dyn Error>> {
@@ -260,8 +447,114 @@ fn main() -> Result<(), Box<dyn Error>> {
     //let xml_content = fs::read_to_string("/Users/david/data_projects/cap-alerts/data/20250705_162303/IpawsArchivedAlerts_2025-01_001.xml")?;
 
     // Deserialize the XML string into the Alert struct
-    let alert: Alert = from_str(&xml_string)?;
+    let (alert, warnings) = parse(xml_string, ParseMode::Lenient)?;
 
     println!("{:?}", alert);
+    println!("detected CAP version: {:?}", alert.version);
+    if !warnings.is_empty() {
+        println!("parse warnings: {:?}", warnings);
+    }
+
+    match alert.verify_signature(xml_string) {
+        Ok(Some(result)) => println!("signature verification: {:?}", result),
+        Ok(None) => println!("alert carries no Signature element"),
+        Err(e) => println!("signature verification error: {e}"),
+    }
+
+    let built = Alert::builder()
+        .identifier("example-1")
+        .sender("example@example.com")
+        .sent(*alert.sent.as_datetime().expect("sample alert's sent timestamp parses"))
+        .status(Status::Test)
+        .msg_type(MsgType::Alert)
+        .scope(Scope::Public)
+        .build()?;
+    println!("round-tripped alert: {}", built.to_xml());
+
     Ok(())
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn info_with(effective: Option<&str>, onset: Option<&str>, expires: Option<&str>) -> AlertInfo {
+        AlertInfo {
+            language: "en-US".to_string(),
+            category: vec![],
+            event: "Test".to_string(),
+            response_type: None,
+            urgency: Urgency::Immediate,
+            severity: Severity::Extreme,
+            certainty: Certainty::Observed,
+            audience: None,
+            event_codes: None,
+            effective: effective.map(|t| CapTimestamp::Raw(t.to_string())),
+            onset: onset.map(|t| CapTimestamp::Raw(t.to_string())),
+            expires: expires.map(|t| CapTimestamp::Raw(t.to_string())),
+            sender_name: None,
+            headline: None,
+            description: None,
+            instruction: None,
+            web: None,
+            contact: None,
+            parameter: None,
+            resource: None,
+            area: None,
+        }
+    }
+
+    fn dt(text: &str) -> chrono::DateTime<chrono::FixedOffset> {
+        chrono::DateTime::parse_from_rfc3339(text).unwrap()
+    }
+
+    fn parsed(text: &str) -> CapTimestamp {
+        CapTimestamp::Parsed(dt(text))
+    }
+
+    #[test]
+    fn is_expired_is_false_with_no_expires_field() {
+        let info = info_with(None, None, None);
+        assert!(!info.is_expired(dt("2025-01-01T00:00:00Z")));
+    }
+
+    #[test]
+    fn is_expired_compares_expires_against_now() {
+        let mut info = info_with(None, None, None);
+        info.expires = Some(parsed("2025-01-01T00:00:00Z"));
+        assert!(info.is_expired(dt("2025-06-01T00:00:00Z")));
+        assert!(!info.is_expired(dt("2024-06-01T00:00:00Z")));
+    }
+
+    #[test]
+    fn effective_range_falls_back_to_onset_when_effective_is_absent() {
+        let mut info = info_with(None, None, None);
+        info.onset = Some(parsed("2025-01-01T00:00:00Z"));
+        info.expires = Some(parsed("2025-01-02T00:00:00Z"));
+        let (start, end) = info.effective_range().expect("onset stands in for effective");
+        assert_eq!(start, dt("2025-01-01T00:00:00Z"));
+        assert_eq!(end, Some(dt("2025-01-02T00:00:00Z")));
+    }
+
+    #[test]
+    fn effective_range_is_none_without_effective_or_onset() {
+        assert!(info_with(None, None, None).effective_range().is_none());
+    }
+
+    #[test]
+    fn duration_until_onset_is_none_once_onset_has_passed() {
+        let mut info = info_with(None, None, None);
+        info.onset = Some(parsed("2025-01-01T00:00:00Z"));
+        assert!(info.duration_until_onset(dt("2025-06-01T00:00:00Z")).is_none());
+    }
+
+    #[test]
+    fn duration_until_onset_returns_remaining_time_before_onset() {
+        let mut info = info_with(None, None, None);
+        info.onset = Some(parsed("2025-01-02T00:00:00Z"));
+        let remaining = info
+            .duration_until_onset(dt("2025-01-01T00:00:00Z"))
+            .expect("onset is still in the future");
+        assert_eq!(remaining, Duration::days(1));
+    }
+}
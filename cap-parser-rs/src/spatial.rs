@@ -0,0 +1,248 @@
+//! Typed geometry for `Area::polygon`, `Area::circle`, and point-in-area
+//! queries.
+//!
+//! CAP encodes coordinates as whitespace-separated `"lat,lon"` pairs (in
+//! that order — latitude first) for polygons, and a single `"lat,lon
+//! radius"` triple, radius in kilometers, for circles.
+
+use std::fmt;
+
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct Point {
+    pub lat: f64,
+    pub lon: f64,
+}
+
+#[derive(Debug, Clone, PartialEq)]
+pub struct Polygon {
+    /// Closed ring: `points[0] == points[points.len() - 1]`.
+    pub points: Vec<Point>,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct Circle {
+    pub center: Point,
+    pub radius_km: f64,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct BoundingBox {
+    pub min_lat: f64,
+    pub max_lat: f64,
+    pub min_lon: f64,
+    pub max_lon: f64,
+}
+
+#[derive(Debug)]
+pub enum GeometryError {
+    /// A `"lat,lon"` pair didn't parse as two floats.
+    BadCoordinate(String),
+    /// A polygon's first and last points didn't match.
+    UnclosedPolygon,
+    /// Fewer than 4 points counting the closing point (3 before closure),
+    /// which can't enclose an area.
+    TooFewPoints,
+    /// A circle string wasn't `"lat,lon radius"`.
+    BadCircle(String),
+}
+
+impl fmt::Display for GeometryError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            GeometryError::BadCoordinate(s) => write!(f, "invalid coordinate pair: {s}"),
+            GeometryError::UnclosedPolygon => write!(f, "polygon's first and last points don't match"),
+            GeometryError::TooFewPoints => write!(f, "polygon has fewer than 3 points"),
+            GeometryError::BadCircle(s) => write!(f, "invalid circle: {s}"),
+        }
+    }
+}
+
+impl std::error::Error for GeometryError {}
+
+fn parse_point(pair: &str) -> Result<Point, GeometryError> {
+    let (lat, lon) = pair
+        .split_once(',')
+        .ok_or_else(|| GeometryError::BadCoordinate(pair.to_string()))?;
+    let lat = lat
+        .trim()
+        .parse()
+        .map_err(|_| GeometryError::BadCoordinate(pair.to_string()))?;
+    let lon = lon
+        .trim()
+        .parse()
+        .map_err(|_| GeometryError::BadCoordinate(pair.to_string()))?;
+    Ok(Point { lat, lon })
+}
+
+impl Polygon {
+    /// Parses CAP's `"lat,lon lat,lon ..."` polygon text, validating that
+    /// the ring is closed (first point equals last) and has at least 4
+    /// points counting the closing one. Points are not checked for being
+    /// distinct.
+    pub fn parse(text: &str) -> Result<Self, GeometryError> {
+        let points = text
+            .split_whitespace()
+            .map(parse_point)
+            .collect::<Result<Vec<_>, _>>()?;
+
+        if points.len() < 4 {
+            return Err(GeometryError::TooFewPoints);
+        }
+        if points.first() != points.last() {
+            return Err(GeometryError::UnclosedPolygon);
+        }
+
+        Ok(Polygon { points })
+    }
+
+    /// Point-in-polygon test via ray casting. Points exactly on an edge
+    /// may resolve either way, which is standard for this algorithm.
+    pub fn contains(&self, lat: f64, lon: f64) -> bool {
+        let points = &self.points;
+        let n = points.len();
+        if n == 0 {
+            return false;
+        }
+
+        let mut inside = false;
+        let mut j = n - 1;
+        for i in 0..n {
+            let pi = points[i];
+            let pj = points[j];
+            let crosses = (pi.lat > lat) != (pj.lat > lat);
+            if crosses {
+                let x_intersect = (pj.lon - pi.lon) * (lat - pi.lat) / (pj.lat - pi.lat) + pi.lon;
+                if lon < x_intersect {
+                    inside = !inside;
+                }
+            }
+            j = i;
+        }
+        inside
+    }
+
+    pub fn bounding_box(&self) -> BoundingBox {
+        bounding_box_of(self.points.iter().copied())
+    }
+}
+
+impl Circle {
+    /// Parses CAP's `"lat,lon radius"` circle text; radius is in
+    /// kilometers.
+    pub fn parse(text: &str) -> Result<Self, GeometryError> {
+        let (point, radius) = text
+            .trim()
+            .rsplit_once(' ')
+            .ok_or_else(|| GeometryError::BadCircle(text.to_string()))?;
+        let center = parse_point(point)?;
+        let radius_km = radius
+            .trim()
+            .parse()
+            .map_err(|_| GeometryError::BadCircle(text.to_string()))?;
+        Ok(Circle { center, radius_km })
+    }
+
+    /// Point-in-circle test via great-circle (haversine) distance.
+    pub fn contains(&self, lat: f64, lon: f64) -> bool {
+        haversine_km(self.center.lat, self.center.lon, lat, lon) <= self.radius_km
+    }
+
+    pub fn bounding_box(&self) -> BoundingBox {
+        // A flat degrees-per-km approximation is good enough for a quick
+        // bounding box; callers doing precise spatial joins should use the
+        // `geo` feature instead.
+        let lat_delta = self.radius_km / 111.0;
+        let lon_delta = self.radius_km / (111.0 * self.center.lat.to_radians().cos()).abs();
+        BoundingBox {
+            min_lat: self.center.lat - lat_delta,
+            max_lat: self.center.lat + lat_delta,
+            min_lon: self.center.lon - lon_delta,
+            max_lon: self.center.lon + lon_delta,
+        }
+    }
+}
+
+fn bounding_box_of(points: impl Iterator<Item = Point>) -> BoundingBox {
+    let mut bbox = BoundingBox {
+        min_lat: f64::INFINITY,
+        max_lat: f64::NEG_INFINITY,
+        min_lon: f64::INFINITY,
+        max_lon: f64::NEG_INFINITY,
+    };
+    for p in points {
+        bbox.min_lat = bbox.min_lat.min(p.lat);
+        bbox.max_lat = bbox.max_lat.max(p.lat);
+        bbox.min_lon = bbox.min_lon.min(p.lon);
+        bbox.max_lon = bbox.max_lon.max(p.lon);
+    }
+    bbox
+}
+
+/// Great-circle distance between two points, in kilometers.
+fn haversine_km(lat1: f64, lon1: f64, lat2: f64, lon2: f64) -> f64 {
+    const EARTH_RADIUS_KM: f64 = 6371.0;
+    let (lat1, lat2) = (lat1.to_radians(), lat2.to_radians());
+    let d_lat = lat2 - lat1;
+    let d_lon = (lon2 - lon1).to_radians();
+    let a = (d_lat / 2.0).sin().powi(2) + lat1.cos() * lat2.cos() * (d_lon / 2.0).sin().powi(2);
+    EARTH_RADIUS_KM * 2.0 * a.sqrt().asin()
+}
+
+#[cfg(feature = "geo")]
+impl From<Polygon> for geo_types::Geometry<f64> {
+    fn from(polygon: Polygon) -> Self {
+        let coords: Vec<geo_types::Coord<f64>> = polygon
+            .points
+            .iter()
+            .map(|p| geo_types::Coord { x: p.lon, y: p.lat })
+            .collect();
+        geo_types::Geometry::Polygon(geo_types::Polygon::new(geo_types::LineString(coords), vec![]))
+    }
+}
+
+#[cfg(feature = "geo")]
+impl From<Circle> for geo_types::Geometry<f64> {
+    fn from(circle: Circle) -> Self {
+        // `geo_types` has no native circle primitive; approximate with a
+        // point plus the radius is left to the caller (e.g. via
+        // `geo::Buffer`), so we surface the center.
+        geo_types::Geometry::Point(geo_types::Point::new(circle.center.lon, circle.center.lat))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const SQUARE: &str = "0,0 0,10 10,10 10,0 0,0";
+
+    #[test]
+    fn polygon_contains_a_point_inside_the_ring() {
+        let polygon = Polygon::parse(SQUARE).expect("closed ring with 4 distinct points");
+        assert!(polygon.contains(5.0, 5.0));
+    }
+
+    #[test]
+    fn polygon_rejects_a_point_outside_the_ring() {
+        let polygon = Polygon::parse(SQUARE).expect("closed ring with 4 distinct points");
+        assert!(!polygon.contains(20.0, 20.0));
+    }
+
+    #[test]
+    fn polygon_contains_does_not_panic_on_an_empty_ring() {
+        let polygon = Polygon { points: vec![] };
+        assert!(!polygon.contains(0.0, 0.0));
+    }
+
+    #[test]
+    fn circle_contains_a_point_within_its_radius() {
+        let circle = Circle::parse("0,0 100").expect("valid circle text");
+        assert!(circle.contains(0.5, 0.5));
+    }
+
+    #[test]
+    fn circle_rejects_a_point_beyond_its_radius() {
+        let circle = Circle::parse("0,0 10").expect("valid circle text");
+        assert!(!circle.contains(45.0, 45.0));
+    }
+}
@@ -0,0 +1,230 @@
+//! Detects which CAP revision an `<alert>` document declares via its root
+//! `xmlns`, so callers know which fields to trust.
+//!
+//! The element set is nearly identical across revisions, but not quite:
+//!
+//! - **1.0** (`http://www.incident.com/cap/1.0`) has no `responseType`
+//!   element at all, and its `category` vocabulary lacks `Fire`, `Health`,
+//!   `CBRNE`, and `Security` (added in later revisions).
+//! - **1.1** (`urn:oasis:names:tc:emergency:cap:1.1`) adds `responseType`
+//!   and those extra `category` values, but its `responseType` vocabulary
+//!   lacks `AllClear` and `None` (added in 1.2).
+//! - **1.2** (`urn:oasis:names:tc:emergency:cap:1.2`) is the full element
+//!   and vocabulary set this crate otherwise documents.
+//!
+//! A token that is in the 1.2 vocabulary but not in the document's declared
+//! revision still deserializes into its [`crate::vocab`] variant; it is
+//! [`version_warnings`] that flags it, so [`crate::ParseMode::Strict`]
+//! rejects the document and [`crate::ParseMode::Lenient`] reports it as a
+//! [`crate::Warning`]. [`CapVersion`] also lets callers that care (e.g.
+//! re-emitting a document) tell which revision it actually came from.
+
+/// The CAP revision an `<alert>` document was declared under, detected from
+/// its root `xmlns`.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum CapVersion {
+    V1_0,
+    V1_1,
+    V1_2,
+    /// The root element declared a namespace this crate doesn't recognize
+    /// as a CAP revision, or declared none at all.
+    Unrecognized(String),
+}
+
+impl Default for CapVersion {
+    /// CAP 1.2 is the common case, so it is the version an `Alert` carries
+    /// when [`detect`] was not run on its source (e.g. when it was
+    /// deserialized directly, since the field is skipped by serde).
+    /// [`crate::parse`] always stores the detected version instead, so a
+    /// document with no detectable namespace gets `Unrecognized` there.
+    fn default() -> Self {
+        CapVersion::V1_2
+    }
+}
+
+impl CapVersion {
+    /// The CAP wire namespace for this revision, or the raw unrecognized
+    /// namespace string.
+    pub fn namespace(&self) -> &str {
+        match self {
+            CapVersion::V1_0 => CAP_1_0_NAMESPACE,
+            CapVersion::V1_1 => CAP_1_1_NAMESPACE,
+            CapVersion::V1_2 => CAP_1_2_NAMESPACE,
+            CapVersion::Unrecognized(raw) => raw.as_str(),
+        }
+    }
+}
+
+pub const CAP_1_0_NAMESPACE: &str = "http://www.incident.com/cap/1.0";
+pub const CAP_1_1_NAMESPACE: &str = "urn:oasis:names:tc:emergency:cap:1.1";
+pub const CAP_1_2_NAMESPACE: &str = "urn:oasis:names:tc:emergency:cap:1.2";
+
+/// Finds the root `<alert>` element's default namespace declaration and
+/// maps it to a [`CapVersion`]. Uses `quick_xml` to read just the root
+/// start tag rather than a text scan, so a namespace prefix (`<cap:alert
+/// xmlns:cap="...">`) or a single-quoted attribute value is recognized the
+/// same as the unprefixed, double-quoted form IPAWS always emits.
+pub fn detect(xml: &str) -> CapVersion {
+    let mut reader = quick_xml::Reader::from_str(xml);
+    let mut buf = Vec::new();
+    loop {
+        let event = match reader.read_event_into(&mut buf) {
+            Ok(event) => event,
+            Err(_) => return CapVersion::Unrecognized(String::new()),
+        };
+        match event {
+            quick_xml::events::Event::Start(start) | quick_xml::events::Event::Empty(start) => {
+                return namespace_of(&start)
+                    .map(classify)
+                    .unwrap_or_else(|| CapVersion::Unrecognized(String::new()));
+            }
+            quick_xml::events::Event::Eof => return CapVersion::Unrecognized(String::new()),
+            _ => {}
+        }
+        buf.clear();
+    }
+}
+
+/// The root element's namespace: its `xmlns` attribute if declared with no
+/// prefix, or the value of `xmlns:<prefix>` if the root element's own name
+/// uses that prefix.
+fn namespace_of(start: &quick_xml::events::BytesStart) -> Option<String> {
+    let prefix = start.name().prefix().map(|p| String::from_utf8_lossy(p.as_ref()).into_owned());
+    for attr in start.attributes().flatten() {
+        let key = String::from_utf8_lossy(attr.key.as_ref()).into_owned();
+        let matches = match &prefix {
+            Some(prefix) => key == format!("xmlns:{prefix}"),
+            None => key == "xmlns",
+        };
+        if matches {
+            return attr.unescape_value().ok().map(|v| v.into_owned());
+        }
+    }
+    None
+}
+
+fn classify(namespace: String) -> CapVersion {
+    match namespace.as_str() {
+        CAP_1_0_NAMESPACE => CapVersion::V1_0,
+        CAP_1_1_NAMESPACE => CapVersion::V1_1,
+        CAP_1_2_NAMESPACE => CapVersion::V1_2,
+        _ => CapVersion::Unrecognized(namespace),
+    }
+}
+
+/// Flags fields that are populated but not valid for the alert's detected
+/// [`CapVersion`], per the revision differences in the module docs above.
+/// Like [`crate::collect_vocabulary_warnings`], this doesn't fail the
+/// parse itself — it's meant to be folded into [`crate::Warning`]s so
+/// [`crate::ParseMode::Strict`] can reject the mismatch and
+/// [`crate::ParseMode::Lenient`] callers can still see it flagged.
+pub fn version_warnings(alert: &crate::Alert) -> Vec<crate::Warning> {
+    let mut warnings = Vec::new();
+    for (i, info) in alert.info.iter().flatten().enumerate() {
+        if alert.version == CapVersion::V1_0 && info.response_type.is_some() {
+            warnings.push(crate::Warning {
+                path: format!("info[{i}].responseType"),
+                raw_value: "responseType does not exist in CAP 1.0".to_string(),
+            });
+        }
+
+        for (j, category) in info.category.iter().enumerate() {
+            if alert.version == CapVersion::V1_0 && is_post_1_0_category(category) {
+                warnings.push(crate::Warning {
+                    path: format!("info[{i}].category[{j}]"),
+                    raw_value: format!(
+                        "{} is not part of the CAP 1.0 category vocabulary",
+                        category.as_token()
+                    ),
+                });
+            }
+        }
+
+        if let Some(response_type) = &info.response_type {
+            if alert.version != CapVersion::V1_2 && is_1_2_only_response_type(response_type) {
+                warnings.push(crate::Warning {
+                    path: format!("info[{i}].responseType"),
+                    raw_value: format!(
+                        "{} was added to the responseType vocabulary in CAP 1.2",
+                        response_type.as_token()
+                    ),
+                });
+            }
+        }
+    }
+    warnings
+}
+
+fn is_post_1_0_category(category: &crate::vocab::Category) -> bool {
+    use crate::vocab::Category;
+    matches!(category, Category::Fire | Category::Health | Category::CBRNE | Category::Security)
+}
+
+fn is_1_2_only_response_type(response_type: &crate::vocab::ResponseType) -> bool {
+    use crate::vocab::ResponseType;
+    matches!(response_type, ResponseType::AllClear | ResponseType::None)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{parse, ParseMode};
+
+    #[test]
+    fn detect_recognizes_an_unprefixed_double_quoted_namespace() {
+        let xml = r#"<alert xmlns="urn:oasis:names:tc:emergency:cap:1.1"></alert>"#;
+        assert_eq!(detect(xml), CapVersion::V1_1);
+    }
+
+    #[test]
+    fn detect_recognizes_a_single_quoted_namespace() {
+        let xml = "<alert xmlns='http://www.incident.com/cap/1.0'></alert>";
+        assert_eq!(detect(xml), CapVersion::V1_0);
+    }
+
+    #[test]
+    fn detect_recognizes_a_prefixed_namespace_declaration() {
+        let xml = r#"<cap:alert xmlns:cap="urn:oasis:names:tc:emergency:cap:1.2"></cap:alert>"#;
+        assert_eq!(detect(xml), CapVersion::V1_2);
+    }
+
+    #[test]
+    fn detect_falls_back_to_unrecognized_without_a_namespace() {
+        assert_eq!(detect("<alert></alert>"), CapVersion::Unrecognized(String::new()));
+    }
+
+    const CAP_1_0_ALERT: &str = r#"
+        <alert xmlns="http://www.incident.com/cap/1.0">
+            <identifier>id-1</identifier>
+            <sender>sender@example.com</sender>
+            <sent>2025-01-30T14:58:26-05:00</sent>
+            <status>Actual</status>
+            <msgType>Alert</msgType>
+            <scope>Public</scope>
+            <info>
+                <language>en-US</language>
+                <category>Fire</category>
+                <event>Wildfire</event>
+                <urgency>Immediate</urgency>
+                <severity>Extreme</severity>
+                <certainty>Observed</certainty>
+            </info>
+        </alert>
+    "#;
+
+    #[test]
+    fn version_warnings_flags_a_category_not_in_cap_1_0() {
+        let (alert, warnings) = parse(CAP_1_0_ALERT, ParseMode::Lenient).expect("parses leniently");
+        assert_eq!(alert.version, CapVersion::V1_0);
+        assert!(warnings.iter().any(|w| w.path == "info[0].category[0]"));
+    }
+
+    #[test]
+    fn version_warnings_rejects_a_cap_1_0_document_in_strict_mode() {
+        assert!(matches!(
+            parse(CAP_1_0_ALERT, ParseMode::Strict),
+            Err(crate::ParseError::UnknownVocabulary(_))
+        ));
+    }
+}
+
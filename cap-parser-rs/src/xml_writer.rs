@@ -0,0 +1,206 @@
+//! Hand-rolled CAP 1.2 XML re-emission.
+//!
+//! `quick_xml`'s generic serializer doesn't give us control over namespace
+//! placement or the exact escaping CAP documents need, so `to_xml()` walks
+//! the parsed structures and writes the wire format directly — the same
+//! approach [`crate::signature`] already uses for canonicalization.
+
+use crate::{Alert, AlertInfo, Area, Resource, Value};
+
+const CAP_1_2_NAMESPACE: &str = "urn:oasis:names:tc:emergency:cap:1.2";
+
+/// Escapes text content for inclusion between XML tags: `&`, `<`, `>`.
+fn escape_text(s: &str) -> String {
+    s.replace('&', "&amp;").replace('<', "&lt;").replace('>', "&gt;")
+}
+
+fn element(out: &mut String, tag: &str, text: &str) {
+    out.push('<');
+    out.push_str(tag);
+    out.push('>');
+    out.push_str(&escape_text(text));
+    out.push_str("</");
+    out.push_str(tag);
+    out.push('>');
+}
+
+fn optional_element(out: &mut String, tag: &str, text: &Option<String>) {
+    if let Some(text) = text {
+        element(out, tag, text);
+    }
+}
+
+fn value_element(out: &mut String, tag: &str, value: &Value) {
+    out.push('<');
+    out.push_str(tag);
+    out.push('>');
+    element(out, "valueName", &value.value_name);
+    element(out, "value", &value.value);
+    out.push_str("</");
+    out.push_str(tag);
+    out.push('>');
+}
+
+impl Alert {
+    /// Serializes this alert back to namespace-correct CAP 1.2 XML.
+    ///
+    /// The previously-parsed `<Signature>`, if any, is not re-emitted, and
+    /// no new signature is generated (see [`crate::signature`] for
+    /// verification only).
+    pub fn to_xml(&self) -> String {
+        let mut out = String::new();
+        out.push_str(r#"<?xml version="1.0" encoding="UTF-8"?>"#);
+        out.push_str(&format!(r#"<alert xmlns="{CAP_1_2_NAMESPACE}">"#));
+
+        element(&mut out, "identifier", &self.identifier);
+        element(&mut out, "sender", &self.sender);
+        element(&mut out, "sent", &self.sent.as_text());
+        element(&mut out, "status", self.status.as_token());
+        element(&mut out, "msgType", self.msg_type.as_token());
+        optional_element(&mut out, "source", &self.source);
+        element(&mut out, "scope", self.scope.as_token());
+        optional_element(&mut out, "restriction", &self.restriction);
+        optional_element(&mut out, "addresses", &self.addresses);
+        for code in self.code.iter().flatten() {
+            element(&mut out, "code", code);
+        }
+        optional_element(&mut out, "note", &self.note);
+        optional_element(&mut out, "references", &self.references);
+        optional_element(&mut out, "incidents", &self.incidents);
+        for info in self.info.iter().flatten() {
+            write_info(&mut out, info);
+        }
+        // The enveloped `<Signature>` is already canonical XML-DSig
+        // output produced by the signer, not data we own the shape of;
+        // re-serializing it field-by-field risks invalidating the
+        // signature it contains, so it's intentionally not re-emitted
+        // here. Callers that need to carry it forward should append the
+        // original `<Signature>` text themselves.
+
+        out.push_str("</alert>");
+        out
+    }
+}
+
+fn write_info(out: &mut String, info: &AlertInfo) {
+    out.push_str("<info>");
+    element(out, "language", &info.language);
+    for category in &info.category {
+        element(out, "category", category.as_token());
+    }
+    element(out, "event", &info.event);
+    if let Some(response_type) = &info.response_type {
+        element(out, "responseType", response_type.as_token());
+    }
+    element(out, "urgency", info.urgency.as_token());
+    element(out, "severity", info.severity.as_token());
+    element(out, "certainty", info.certainty.as_token());
+    optional_element(out, "audience", &info.audience);
+    for event_code in info.event_codes.iter().flatten() {
+        value_element(out, "eventCode", event_code);
+    }
+    if let Some(effective) = &info.effective {
+        element(out, "effective", &effective.as_text());
+    }
+    if let Some(onset) = &info.onset {
+        element(out, "onset", &onset.as_text());
+    }
+    if let Some(expires) = &info.expires {
+        element(out, "expires", &expires.as_text());
+    }
+    optional_element(out, "senderName", &info.sender_name);
+    optional_element(out, "headline", &info.headline);
+    optional_element(out, "description", &info.description);
+    optional_element(out, "instruction", &info.instruction);
+    optional_element(out, "web", &info.web);
+    optional_element(out, "contact", &info.contact);
+    for parameter in info.parameter.iter().flatten() {
+        value_element(out, "parameter", parameter);
+    }
+    for resource in info.resource.iter().flatten() {
+        write_resource(out, resource);
+    }
+    for area in info.area.iter().flatten() {
+        write_area(out, area);
+    }
+    out.push_str("</info>");
+}
+
+fn write_resource(out: &mut String, resource: &Resource) {
+    out.push_str("<resource>");
+    element(out, "resourceDesc", &resource.resource_desc);
+    element(out, "mimeType", &resource.mime_type);
+    optional_element(out, "size", &resource.size);
+    optional_element(out, "uri", &resource.uri);
+    optional_element(out, "derefUri", &resource.deref_uri);
+    optional_element(out, "digest", &resource.digest);
+    out.push_str("</resource>");
+}
+
+fn write_area(out: &mut String, area: &Area) {
+    out.push_str("<area>");
+    element(out, "areaDesc", &area.area_desc);
+    for polygon in area.polygon.iter().flatten() {
+        element(out, "polygon", polygon);
+    }
+    for circle in area.circle.iter().flatten() {
+        element(out, "circle", circle);
+    }
+    for geocode in area.geocode.iter().flatten() {
+        value_element(out, "geocode", geocode);
+    }
+    optional_element(out, "altitude", &area.altitude);
+    optional_element(out, "ceiling", &area.ceiling);
+    out.push_str("</area>");
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::{parse, vocab::*, Alert, AlertInfo, ParseMode};
+    use chrono::DateTime;
+
+    #[test]
+    fn to_xml_round_trips_through_parse() {
+        let info = AlertInfo {
+            language: "en-US".to_string(),
+            category: vec![Category::Met],
+            event: "Flood Warning".to_string(),
+            response_type: None,
+            urgency: Urgency::Immediate,
+            severity: Severity::Severe,
+            certainty: Certainty::Observed,
+            audience: Some("Public".to_string()),
+            event_codes: None,
+            effective: None,
+            onset: None,
+            expires: None,
+            sender_name: None,
+            headline: None,
+            description: None,
+            instruction: None,
+            web: None,
+            contact: None,
+            parameter: None,
+            resource: None,
+            area: None,
+        };
+
+        let built = Alert::builder()
+            .identifier("example-1")
+            .sender("example@example.com")
+            .sent(DateTime::parse_from_rfc3339("2025-01-30T14:58:26-05:00").unwrap())
+            .status(Status::Actual)
+            .msg_type(MsgType::Alert)
+            .scope(Scope::Public)
+            .info(info)
+            .build()
+            .expect("all required fields are set");
+
+        let xml = built.to_xml();
+        let (reparsed, _warnings) = parse(&xml, ParseMode::Lenient).expect("emitted XML reparses");
+
+        assert_eq!(reparsed.identifier, "example-1");
+        let info = &reparsed.info.expect("info block survives the round trip")[0];
+        assert_eq!(info.audience.as_deref(), Some("Public"));
+    }
+}